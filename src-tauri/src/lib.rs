@@ -1,62 +1,202 @@
+use std::collections::HashMap;
 use std::fs;
-use std::io::Write;
+use std::fs::File;
+use std::io::{Read, Write};
 use std::path::Path;
+
+use sha2::{Digest, Sha256};
 use tauri::command;
 
+mod zip_export;
+mod zip_protocol;
+use zip_export::{export_project_zip, ManifestEntry, MANIFEST_NAME};
+
+const IMPORT_CHUNK_SIZE: usize = 64 * 1024;
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
-// 1. Define the Input Structure for the Zip Command
-#[derive(serde::Deserialize)]
-struct ZipFileEntry {
-    path: String,                // The name of the file inside the zip (e.g., "media/video.mp4")
-    content: Option<String>,     // For text files: The actual string content
-    source_path: Option<String>, // For large files: The path on disk to stream from
+// Resolves `entry_name` against `dest_dir` and rejects Zip-Slip attempts: entries whose
+// normalized path (after collapsing `..` components) would land outside `dest_dir`.
+fn resolve_entry_path(dest_dir: &Path, entry_name: &str) -> Result<std::path::PathBuf, String> {
+    let mut resolved = dest_dir.to_path_buf();
+    for component in Path::new(entry_name).components() {
+        match component {
+            std::path::Component::Normal(part) => resolved.push(part),
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                if !resolved.pop() {
+                    return Err(format!("Zip entry escapes destination: {}", entry_name));
+                }
+            }
+            _ => return Err(format!("Unsupported path component in zip entry: {}", entry_name)),
+        }
+    }
+    // Catch a `..` escape before any directory is created for it.
+    if !resolved.starts_with(dest_dir) {
+        return Err(format!("Zip entry escapes destination: {}", entry_name));
+    }
+
+    let canonical_dest = fs::canonicalize(dest_dir)
+        .map_err(|e| format!("Failed to resolve destination {}: {}", dest_dir.display(), e))?;
+    let parent = resolved
+        .parent()
+        .ok_or_else(|| format!("Zip entry has no parent: {}", entry_name))?;
+    fs::create_dir_all(parent)
+        .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+    let canonical_parent = fs::canonicalize(parent)
+        .map_err(|e| format!("Failed to resolve directory {}: {}", parent.display(), e))?;
+    if !canonical_parent.starts_with(&canonical_dest) {
+        return Err(format!("Zip entry escapes destination: {}", entry_name));
+    }
+
+    Ok(resolved)
 }
 
-// 2. The Export Command (Runs on a separate thread to prevent UI freezing)
+// 3. The Import Command: the inverse of `export_project_zip`, extracting an archive back to disk.
 #[command]
-async fn export_project_zip(zip_path: String, entries: Vec<ZipFileEntry>) -> Result<(), String> {
+async fn import_project_zip(zip_path: String, dest_dir: String) -> Result<Vec<String>, String> {
     tauri::async_runtime::spawn_blocking(move || {
-        let path = Path::new(&zip_path);
-        
-        // Create the zip file
-        let file = File::create(&path).map_err(|e| format!("Failed to create zip file: {}", e))?;
-        let mut zip = zip::ZipWriter::new(file);
-        
-        // FIX: Use SimpleFileOptions to avoid "E0283 type annotations needed"
-        let options = zip::write::SimpleFileOptions::default()
-            .compression_method(zip::CompressionMethod::Stored)
-            .unix_permissions(0o755);
-
-        for entry in entries {
-            // Start a new file inside the zip
-            zip.start_file(&entry.path, options)
-                .map_err(|e| format!("Zip error for {}: {}", entry.path, e))?;
-
-            // CASE A: Large File (Stream from Disk)
-            if let Some(src) = entry.source_path {
-                let mut f = File::open(&src).map_err(|e| format!("Failed to open source {}: {}", src, e))?;
-                // Stream copy to avoid loading into RAM
-                std::io::copy(&mut f, &mut zip).map_err(|e| format!("Failed to copy {}: {}", src, e))?;
-            } 
-            // CASE B: Text Content (Write String)
-            else if let Some(content) = entry.content {
-                zip.write_all(content.as_bytes())
-                    .map_err(|e| format!("Failed to write content for {}: {}", entry.path, e))?;
+        let dest = Path::new(&dest_dir);
+        fs::create_dir_all(dest).map_err(|e| format!("Failed to create destination {}: {}", dest_dir, e))?;
+
+        let file = File::open(&zip_path).map_err(|e| format!("Failed to open zip file: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+
+        // Honor a manifest.json written by `export_project_zip(enable_manifest: true)`: if one
+        // is present, each file it lists has its digest checked as it's extracted. The manifest
+        // itself is not extracted, and entries it does not list are extracted unchecked.
+        let manifest: Option<HashMap<String, ManifestEntry>> = match archive.by_name(MANIFEST_NAME) {
+            Ok(mut manifest_file) => {
+                let mut contents = Vec::new();
+                manifest_file
+                    .read_to_end(&mut contents)
+                    .map_err(|e| format!("Failed to read manifest: {}", e))?;
+                let entries: Vec<ManifestEntry> =
+                    serde_json::from_slice(&contents).map_err(|e| format!("Failed to parse manifest: {}", e))?;
+                Some(entries.into_iter().map(|entry| (entry.path.clone(), entry)).collect())
+            }
+            Err(_) => None,
+        };
+
+        let mut extracted_paths = Vec::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let mut zip_entry = archive
+                .by_index(i)
+                .map_err(|e| format!("Failed to read zip entry {}: {}", i, e))?;
+
+            let entry_name = zip_entry.name().to_string();
+            if manifest.is_some() && entry_name == MANIFEST_NAME {
+                continue;
+            }
+            let out_path = resolve_entry_path(dest, &entry_name)?;
+
+            if zip_entry.is_dir() {
+                fs::create_dir_all(&out_path)
+                    .map_err(|e| format!("Failed to create directory {}: {}", out_path.display(), e))?;
+                continue;
+            }
+
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
             }
+
+            let mut out_file = File::create(&out_path)
+                .map_err(|e| format!("Failed to create {}: {}", out_path.display(), e))?;
+
+            let expected = manifest.as_ref().and_then(|m| m.get(&entry_name));
+            let mut hasher = expected.map(|_| Sha256::new());
+            let mut buf = [0u8; IMPORT_CHUNK_SIZE];
+            loop {
+                let n = zip_entry
+                    .read(&mut buf)
+                    .map_err(|e| format!("Failed to extract {}: {}", out_path.display(), e))?;
+                if n == 0 {
+                    break;
+                }
+                out_file
+                    .write_all(&buf[..n])
+                    .map_err(|e| format!("Failed to extract {}: {}", out_path.display(), e))?;
+                if let Some(hasher) = hasher.as_mut() {
+                    hasher.update(&buf[..n]);
+                }
+            }
+
+            if let (Some(entry), Some(hasher)) = (expected, hasher) {
+                let digest: String = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+                if digest != entry.sha256 {
+                    return Err(format!(
+                        "Checksum mismatch for {}: expected {}, got {}",
+                        entry_name, entry.sha256, digest
+                    ));
+                }
+            }
+
+            #[cfg(unix)]
+            if let Some(mode) = zip_entry.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&out_path, fs::Permissions::from_mode(mode))
+                    .map_err(|e| format!("Failed to set permissions on {}: {}", out_path.display(), e))?;
+            }
+
+            extracted_paths.push(out_path.to_string_lossy().into_owned());
         }
 
-        zip.finish().map_err(|e| format!("Failed to finalize zip: {}", e))?;
-        Ok(())
+        Ok(extracted_paths)
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
+#[cfg(test)]
+mod resolve_entry_path_tests {
+    use super::resolve_entry_path;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sync-desktop-poc-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rejects_parent_dir_escape() {
+        let dest = scratch_dir("zip-slip-escape");
+        let result = resolve_entry_path(&dest, "../../etc/passwd");
+        assert!(result.is_err(), "expected a zip-slip escape to be rejected, got {:?}", result);
+    }
+
+    #[test]
+    fn rejects_escape_hidden_behind_a_normal_component() {
+        let dest = scratch_dir("zip-slip-hidden-escape");
+        // "nested/../../escape.txt" pops back out of `dest` entirely even though it starts
+        // with a normal-looking path component.
+        let result = resolve_entry_path(&dest, "nested/../../escape.txt");
+        assert!(result.is_err(), "expected a zip-slip escape to be rejected, got {:?}", result);
+    }
+
+    #[test]
+    fn allows_nested_paths_within_the_destination() {
+        let dest = scratch_dir("zip-slip-nested-ok");
+        let resolved = resolve_entry_path(&dest, "a/b/c.txt").expect("nested path should resolve");
+        assert!(resolved.starts_with(&dest));
+        assert_eq!(resolved, dest.join("a").join("b").join("c.txt"));
+    }
+
+    #[test]
+    fn rejects_absolute_path_components() {
+        let dest = scratch_dir("zip-slip-absolute");
+        let result = resolve_entry_path(&dest, "/etc/passwd");
+        assert!(result.is_err(), "expected an absolute entry path to be rejected, got {:?}", result);
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -66,8 +206,11 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_opener::init())
+        // Lets the frontend point a <video>/<img> at `zip://<archive>/<entry path>` and stream
+        // the entry straight out of the archive instead of extracting it to disk first.
+        .register_uri_scheme_protocol("zip", |_app, request| zip_protocol::handle(&request))
         // Register the new command here alongside 'greet'
-        .invoke_handler(tauri::generate_handler![greet, export_project_zip]) 
+        .invoke_handler(tauri::generate_handler![greet, export_project_zip, import_project_zip])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-}
\ No newline at end of file
+}
@@ -0,0 +1,787 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use tauri::{command, Emitter, Window};
+
+const COPY_CHUNK_SIZE: usize = 64 * 1024;
+
+// Name of the manifest entry written when `enable_manifest` is set on export, and the name
+// `import_project_zip` looks for to opt into verifying extracted files against it.
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+// One row of `manifest.json`: a path inside the archive plus the SHA-256/length an importer
+// can use to detect truncation or tampering.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub sha256: String,
+    pub length: u64,
+}
+
+fn hex_digest(hasher: Sha256) -> String {
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x0807_4b50;
+const CENTRAL_DIR_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR_SIGNATURE: u32 = 0x0605_4b50;
+const UTF8_NAME_FLAG: u16 = 0x0800;
+const STREAMED_SIZES_UNKNOWN_FLAG: u16 = 0x0008;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+const UNIX_FILE_MODE: u32 = 0o100_755;
+const UNIX_DIR_MODE: u32 = 0o040_755;
+const MSDOS_DIR_ATTR: u32 = 0x10;
+
+// The local/central-directory headers this writer emits only have 32-bit size fields (no
+// Zip64 extra field), so any single entry at or above this would either wrap or collide with
+// the 0xFFFFFFFF Zip64 marker and produce a corrupt archive. Refuse instead of writing
+// truncated headers.
+const MAX_ENTRY_SIZE: u64 = u32::MAX as u64;
+
+// 1. Define the Input Structure for the Zip Command
+#[derive(serde::Deserialize)]
+pub struct ZipFileEntry {
+    path: String,                // The name of the file inside the zip (e.g., "media/video.mp4")
+    content: Option<String>,     // For text files: The actual string content
+    source_path: Option<String>, // For large files: The path on disk to stream from
+    directory: Option<String>,   // For folders: a path on disk to walk recursively under `path`
+}
+
+// Progress payload for the `zip-export-progress` event emitted while `export_project_zip` runs.
+#[derive(Clone, serde::Serialize)]
+struct ZipExportProgress {
+    current_index: usize,
+    total_entries: usize,
+    current_path: String,
+    bytes_written: u64,
+    total_bytes: u64,
+}
+
+// Incremental CRC-32 (IEEE 802.3 polynomial), so Stored entries can be streamed in chunks
+// without buffering the whole file just to know its checksum up front.
+struct Crc32State(u32);
+
+impl Crc32State {
+    fn new() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.0 ^= byte as u32;
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    fn finalize(self) -> u32 {
+        !self.0
+    }
+}
+
+fn crc32_of(data: &[u8]) -> u32 {
+    let mut state = Crc32State::new();
+    state.update(data);
+    state.finalize()
+}
+
+// Maps the frontend's compression name onto a method this writer actually implements. Only
+// Stored and Deflate are supported: Bzip2/Zstd would need their own crates to compress raw
+// bytes by hand, so rather than silently downgrade a request for either to an uncompressed
+// archive, this rejects it outright.
+fn parse_compression_method(name: &str) -> Result<u16, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "stored" | "store" | "none" => Ok(METHOD_STORED),
+        "deflate" => Ok(METHOD_DEFLATED),
+        other => Err(format!(
+            "Unsupported compression method \"{}\": only \"stored\" and \"deflate\" are implemented",
+            other
+        )),
+    }
+}
+
+// A minimal hand-rolled ZIP writer. The `zip` crate's `ZipWriter` only compresses what you
+// feed it as it goes, which means only one thread can ever touch it; to let a worker pool
+// compress payloads concurrently we need to be able to append already-compressed bytes
+// ourselves, so this writes local file headers / data descriptors / the central directory
+// directly. Kept deliberately small: it only supports what `export_project_zip` needs
+// (Stored or Deflated entries written with sizes known up front, and Stored entries streamed
+// via a data descriptor).
+struct RawZipWriter<W> {
+    writer: W,
+    offset: u64,
+    records: Vec<CentralDirRecord>,
+}
+
+struct CentralDirRecord {
+    name: String,
+    method: u16,
+    crc32: u32,
+    compressed_size: u32,
+    uncompressed_size: u32,
+    local_header_offset: u32,
+    is_dir: bool,
+}
+
+struct StreamedFile {
+    name: String,
+    local_header_offset: u64,
+    crc: Crc32State,
+    size: u64,
+    hasher: Option<Sha256>,
+}
+
+impl<W: Write> RawZipWriter<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            offset: 0,
+            records: Vec::new(),
+        }
+    }
+
+    fn write_u16(&mut self, v: u16) -> std::io::Result<()> {
+        self.writer.write_all(&v.to_le_bytes())?;
+        self.offset += 2;
+        Ok(())
+    }
+
+    fn write_u32(&mut self, v: u32) -> std::io::Result<()> {
+        self.writer.write_all(&v.to_le_bytes())?;
+        self.offset += 4;
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, data: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(data)?;
+        self.offset += data.len() as u64;
+        Ok(())
+    }
+
+    fn add_directory(&mut self, name: &str) -> std::io::Result<()> {
+        let name = if name.ends_with('/') {
+            name.to_string()
+        } else {
+            format!("{}/", name)
+        };
+        let local_header_offset = self.offset;
+        self.write_local_header(&name, METHOD_STORED, 0, 0, 0, 0)?;
+
+        self.records.push(CentralDirRecord {
+            name,
+            method: METHOD_STORED,
+            crc32: 0,
+            compressed_size: 0,
+            uncompressed_size: 0,
+            local_header_offset: local_header_offset as u32,
+            is_dir: true,
+        });
+        Ok(())
+    }
+
+    // For entries whose compressed bytes and CRC are already known (the worker-pool path).
+    fn add_file(
+        &mut self,
+        name: &str,
+        method: u16,
+        crc32: u32,
+        compressed: &[u8],
+        uncompressed_size: u64,
+    ) -> std::io::Result<()> {
+        if compressed.len() as u64 >= MAX_ENTRY_SIZE || uncompressed_size >= MAX_ENTRY_SIZE {
+            return Err(std::io::Error::other(format!(
+                "Entry {} is too large for this writer (Zip64 is not supported): {} bytes",
+                name,
+                uncompressed_size.max(compressed.len() as u64)
+            )));
+        }
+
+        let local_header_offset = self.offset;
+        self.write_local_header(
+            name,
+            method,
+            0,
+            crc32,
+            compressed.len() as u32,
+            uncompressed_size as u32,
+        )?;
+        self.write_bytes(compressed)?;
+
+        self.records.push(CentralDirRecord {
+            name: name.to_string(),
+            method,
+            crc32,
+            compressed_size: compressed.len() as u32,
+            uncompressed_size: uncompressed_size as u32,
+            local_header_offset: local_header_offset as u32,
+            is_dir: false,
+        });
+        Ok(())
+    }
+
+    // For Stored entries streamed straight from disk: the size/CRC aren't known until the
+    // last chunk, so the header is written with the "sizes follow in a data descriptor" flag
+    // and the real values trail the data instead.
+    fn begin_streamed_file(&mut self, name: &str, hash: bool) -> std::io::Result<StreamedFile> {
+        let local_header_offset = self.offset;
+        self.write_local_header(name, METHOD_STORED, STREAMED_SIZES_UNKNOWN_FLAG, 0, 0, 0)?;
+        Ok(StreamedFile {
+            name: name.to_string(),
+            local_header_offset,
+            crc: Crc32State::new(),
+            size: 0,
+            hasher: hash.then(Sha256::new),
+        })
+    }
+
+    fn write_streamed_chunk(&mut self, file: &mut StreamedFile, data: &[u8]) -> std::io::Result<()> {
+        self.write_bytes(data)?;
+        file.crc.update(data);
+        file.size += data.len() as u64;
+        if let Some(hasher) = &mut file.hasher {
+            hasher.update(data);
+        }
+        Ok(())
+    }
+
+    // Returns the SHA-256 hex digest when the file was started with `hash: true`.
+    fn finish_streamed_file(&mut self, file: StreamedFile) -> std::io::Result<Option<String>> {
+        if file.size >= MAX_ENTRY_SIZE {
+            return Err(std::io::Error::other(format!(
+                "Entry {} is too large for this writer (Zip64 is not supported): {} bytes",
+                file.name, file.size
+            )));
+        }
+
+        let crc32 = file.crc.finalize();
+        self.write_u32(DATA_DESCRIPTOR_SIGNATURE)?;
+        self.write_u32(crc32)?;
+        self.write_u32(file.size as u32)?;
+        self.write_u32(file.size as u32)?;
+
+        self.records.push(CentralDirRecord {
+            name: file.name,
+            method: METHOD_STORED,
+            crc32,
+            compressed_size: file.size as u32,
+            uncompressed_size: file.size as u32,
+            local_header_offset: file.local_header_offset as u32,
+            is_dir: false,
+        });
+        Ok(file.hasher.map(hex_digest))
+    }
+
+    fn write_local_header(
+        &mut self,
+        name: &str,
+        method: u16,
+        flags: u16,
+        crc32: u32,
+        compressed_size: u32,
+        uncompressed_size: u32,
+    ) -> std::io::Result<()> {
+        let name_bytes = name.as_bytes();
+        self.write_u32(LOCAL_FILE_HEADER_SIGNATURE)?;
+        self.write_u16(20)?; // version needed to extract
+        self.write_u16(flags | UTF8_NAME_FLAG)?;
+        self.write_u16(method)?;
+        self.write_u16(0)?; // last mod file time
+        self.write_u16(0)?; // last mod file date
+        self.write_u32(crc32)?;
+        self.write_u32(compressed_size)?;
+        self.write_u32(uncompressed_size)?;
+        self.write_u16(name_bytes.len() as u16)?;
+        self.write_u16(0)?; // extra field length
+        self.write_bytes(name_bytes)
+    }
+
+    // Returns the underlying writer back to the caller (mainly so tests can inspect the bytes
+    // that were written; `export_project_zip` just lets it drop).
+    fn finish(mut self) -> std::io::Result<W> {
+        let central_dir_offset = self.offset;
+        for record in &self.records {
+            let name_bytes = record.name.as_bytes();
+            let unix_mode = if record.is_dir { UNIX_DIR_MODE } else { UNIX_FILE_MODE };
+            let external_attrs = (unix_mode << 16) | if record.is_dir { MSDOS_DIR_ATTR } else { 0 };
+
+            self.write_u32(CENTRAL_DIR_HEADER_SIGNATURE)?;
+            self.write_u16(0x0314)?; // version made by: unix (3) << 8 | spec version 20
+            self.write_u16(20)?; // version needed to extract
+            self.write_u16(UTF8_NAME_FLAG)?;
+            self.write_u16(record.method)?;
+            self.write_u16(0)?; // last mod file time
+            self.write_u16(0)?; // last mod file date
+            self.write_u32(record.crc32)?;
+            self.write_u32(record.compressed_size)?;
+            self.write_u32(record.uncompressed_size)?;
+            self.write_u16(name_bytes.len() as u16)?;
+            self.write_u16(0)?; // extra field length
+            self.write_u16(0)?; // file comment length
+            self.write_u16(0)?; // disk number start
+            self.write_u16(0)?; // internal file attributes
+            self.write_u32(external_attrs)?;
+            self.write_u32(record.local_header_offset)?;
+            self.write_bytes(name_bytes)?;
+        }
+        let central_dir_size = self.offset - central_dir_offset;
+
+        self.write_u32(END_OF_CENTRAL_DIR_SIGNATURE)?;
+        self.write_u16(0)?; // number of this disk
+        self.write_u16(0)?; // disk where central directory starts
+        self.write_u16(self.records.len() as u16)?;
+        self.write_u16(self.records.len() as u16)?;
+        self.write_u32(central_dir_size as u32)?;
+        self.write_u32(central_dir_offset as u32)?;
+        self.write_u16(0)?; // comment length
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+// The flattened unit of work an export walks through, in final archive order. Directories
+// and disk-streamed files are cheap enough that the writer thread just handles them inline;
+// only `Compressible` payloads are worth farming out to the worker pool.
+enum FlatItem {
+    Directory { zip_path: String },
+    StoredFile { zip_path: String, disk_path: String },
+    Compressible { zip_path: String, bytes: Vec<u8>, method: u16, level: Option<i32> },
+}
+
+fn flat_item_size(item: &FlatItem) -> u64 {
+    match item {
+        FlatItem::Directory { .. } => 0,
+        FlatItem::StoredFile { disk_path, .. } => fs::metadata(disk_path).map(|m| m.len()).unwrap_or(0),
+        FlatItem::Compressible { bytes, .. } => bytes.len() as u64,
+    }
+}
+
+// Expands `entries` (including any `directory` entries, walked recursively) into the ordered
+// list of archive members the export will actually write.
+fn flatten_entries(entries: Vec<ZipFileEntry>, content_method: u16, level: Option<i32>) -> Result<Vec<FlatItem>, String> {
+    let mut flat = Vec::new();
+
+    for entry in entries {
+        if let Some(dir) = entry.directory {
+            flatten_directory(Path::new(&dir), &entry.path, &mut flat)?;
+        } else if let Some(src) = entry.source_path {
+            flat.push(FlatItem::StoredFile {
+                zip_path: entry.path,
+                disk_path: src,
+            });
+        } else if let Some(content) = entry.content {
+            flat.push(FlatItem::Compressible {
+                zip_path: entry.path,
+                bytes: content.into_bytes(),
+                method: content_method,
+                level,
+            });
+        }
+    }
+
+    Ok(flat)
+}
+
+fn flatten_directory(root: &Path, zip_prefix: &str, flat: &mut Vec<FlatItem>) -> Result<(), String> {
+    let mut queue = VecDeque::new();
+    queue.push_back((root.to_path_buf(), zip_prefix.trim_end_matches('/').to_string()));
+
+    while let Some((dir, prefix)) = queue.pop_front() {
+        flat.push(FlatItem::Directory {
+            zip_path: format!("{}/", prefix),
+        });
+
+        let read_dir = fs::read_dir(&dir).map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+        for item in read_dir {
+            let item = item.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let file_type = item
+                .file_type()
+                .map_err(|e| format!("Failed to stat {}: {}", item.path().display(), e))?;
+            let name = item.file_name().to_string_lossy().into_owned();
+            let in_zip_path = format!("{}/{}", prefix, name);
+
+            if file_type.is_dir() {
+                queue.push_back((item.path(), in_zip_path));
+            } else {
+                flat.push(FlatItem::StoredFile {
+                    zip_path: in_zip_path,
+                    disk_path: item.path().to_string_lossy().into_owned(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Result of compressing one `Compressible` item on a worker thread.
+struct CompressedResult {
+    index: usize,
+    zip_path: String,
+    method: u16,
+    crc32: u32,
+    compressed: Vec<u8>,
+    uncompressed_size: u64,
+    sha256: Option<String>,
+}
+
+fn compress_payload(bytes: &[u8], method: u16, level: Option<i32>, hash: bool) -> (u16, u32, Vec<u8>, Option<String>) {
+    let crc32 = crc32_of(bytes);
+    let sha256 = hash.then(|| {
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        hex_digest(hasher)
+    });
+    if method == METHOD_DEFLATED {
+        let compression = flate2::Compression::new(level.unwrap_or(6).clamp(0, 9) as u32);
+        let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), compression);
+        // In-memory buffer: writes to a `Vec<u8>` never fail.
+        encoder.write_all(bytes).expect("compressing into an in-memory buffer cannot fail");
+        let compressed = encoder.finish().expect("compressing into an in-memory buffer cannot fail");
+        (METHOD_DEFLATED, crc32, compressed, sha256)
+    } else {
+        (METHOD_STORED, crc32, bytes.to_vec(), sha256)
+    }
+}
+
+// 2. The Export Command (Runs on a separate thread to prevent UI freezing)
+//
+// Compression is the expensive part of an export, so rather than compress entries one at a
+// time on the blocking thread, `Compressible` entries are handed to a pool of worker threads
+// that deflate + CRC them concurrently. Workers report back over an `mpsc` channel tagged with
+// each entry's index; the blocking thread is the only one that touches `RawZipWriter`, and it
+// buffers any results that arrive out of order so the archive is still written deterministically.
+#[command]
+pub async fn export_project_zip(
+    window: Window,
+    zip_path: String,
+    entries: Vec<ZipFileEntry>,
+    compression: Option<String>,
+    level: Option<i32>,
+    enable_manifest: Option<bool>,
+) -> Result<(), String> {
+    let enable_manifest = enable_manifest.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || {
+        let content_method = match compression.as_deref() {
+            Some(name) => parse_compression_method(name)?,
+            None => METHOD_STORED,
+        };
+        let flat = flatten_entries(entries, content_method, level)?;
+        let total_entries = flat.len();
+        // Tracked in the same (uncompressed) unit as `total_bytes` below, so the ratio the
+        // frontend renders can actually reach 1.0 even when entries get compressed down to far
+        // fewer bytes on disk than they started as.
+        let total_bytes: u64 = flat.iter().map(flat_item_size).sum();
+        let mut bytes_processed: u64 = 0;
+        let mut manifest_entries: Vec<ManifestEntry> = Vec::new();
+
+        let file = File::create(Path::new(&zip_path)).map_err(|e| format!("Failed to create zip file: {}", e))?;
+        let mut raw_zip = RawZipWriter::new(file);
+
+        // Hand every Compressible item to a shared work queue up front so the worker pool can
+        // start chewing through it while the writer thread is still handling earlier, cheaper
+        // (directory/Stored) entries in order.
+        let work_queue: VecDeque<(usize, String, Vec<u8>, u16, Option<i32>)> = flat
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| match item {
+                FlatItem::Compressible { zip_path, bytes, method, level } => {
+                    Some((index, zip_path.clone(), bytes.clone(), *method, *level))
+                }
+                _ => None,
+            })
+            .collect();
+        let worker_count = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4).min(work_queue.len().max(1));
+
+        let mut join_handles = Vec::new();
+        let mut pending_results: HashMap<usize, CompressedResult> = HashMap::new();
+        let result_rx = if work_queue.is_empty() {
+            None
+        } else {
+            let queue = Arc::new(std::sync::Mutex::new(work_queue));
+            let (tx, rx) = mpsc::channel::<CompressedResult>();
+
+            for _ in 0..worker_count {
+                let queue = queue.clone();
+                let tx = tx.clone();
+                join_handles.push(std::thread::spawn(move || loop {
+                    let item = {
+                        let mut queue = queue.lock().unwrap();
+                        queue.pop_front()
+                    };
+                    let Some((index, zip_path, bytes, method, level)) = item else {
+                        break;
+                    };
+                    let uncompressed_size = bytes.len() as u64;
+                    let (method, crc32, compressed, sha256) = compress_payload(&bytes, method, level, enable_manifest);
+                    // The receiver outlives every worker, so a send error only happens if the
+                    // writer thread has already bailed out on an earlier error.
+                    let _ = tx.send(CompressedResult {
+                        index,
+                        zip_path,
+                        method,
+                        crc32,
+                        compressed,
+                        uncompressed_size,
+                        sha256,
+                    });
+                }));
+            }
+            drop(tx);
+            Some(rx)
+        };
+
+        for (current_index, item) in flat.into_iter().enumerate() {
+            match item {
+                FlatItem::Directory { zip_path } => {
+                    raw_zip
+                        .add_directory(&zip_path)
+                        .map_err(|e| format!("Zip error for directory {}: {}", zip_path, e))?;
+                }
+                FlatItem::StoredFile { zip_path, disk_path } => {
+                    let mut f = File::open(&disk_path).map_err(|e| format!("Failed to open source {}: {}", disk_path, e))?;
+                    let mut handle = raw_zip
+                        .begin_streamed_file(&zip_path, enable_manifest)
+                        .map_err(|e| format!("Zip error for {}: {}", zip_path, e))?;
+
+                    let mut buf = [0u8; COPY_CHUNK_SIZE];
+                    loop {
+                        let n = f.read(&mut buf).map_err(|e| format!("Failed to read {}: {}", disk_path, e))?;
+                        if n == 0 {
+                            break;
+                        }
+                        raw_zip
+                            .write_streamed_chunk(&mut handle, &buf[..n])
+                            .map_err(|e| format!("Failed to copy {}: {}", disk_path, e))?;
+                        bytes_processed += n as u64;
+                        window
+                            .emit(
+                                "zip-export-progress",
+                                ZipExportProgress {
+                                    current_index,
+                                    total_entries,
+                                    current_path: zip_path.clone(),
+                                    bytes_written: bytes_processed,
+                                    total_bytes,
+                                },
+                            )
+                            .map_err(|e| format!("Failed to emit progress: {}", e))?;
+                    }
+                    let length = handle.size;
+                    if let Some(sha256) = raw_zip
+                        .finish_streamed_file(handle)
+                        .map_err(|e| format!("Zip error for {}: {}", zip_path, e))?
+                    {
+                        manifest_entries.push(ManifestEntry {
+                            path: zip_path.clone(),
+                            sha256,
+                            length,
+                        });
+                    }
+                }
+                FlatItem::Compressible { zip_path, .. } => {
+                    // Results can arrive out of order since workers race each other; keep
+                    // pulling from the channel and stashing anything that isn't ours yet
+                    // until the one for `current_index` actually shows up.
+                    while !pending_results.contains_key(&current_index) {
+                        let rx = result_rx
+                            .as_ref()
+                            .ok_or_else(|| format!("Missing compression result for {}", zip_path))?;
+                        let result = rx
+                            .recv()
+                            .map_err(|_| format!("Missing compression result for {}", zip_path))?;
+                        pending_results.insert(result.index, result);
+                    }
+                    let result = pending_results.remove(&current_index).unwrap();
+                    raw_zip
+                        .add_file(&result.zip_path, result.method, result.crc32, &result.compressed, result.uncompressed_size)
+                        .map_err(|e| format!("Zip error for {}: {}", result.zip_path, e))?;
+                    if let Some(sha256) = result.sha256.clone() {
+                        manifest_entries.push(ManifestEntry {
+                            path: result.zip_path.clone(),
+                            sha256,
+                            length: result.uncompressed_size,
+                        });
+                    }
+                    bytes_processed += result.uncompressed_size;
+                    window
+                        .emit(
+                            "zip-export-progress",
+                            ZipExportProgress {
+                                current_index,
+                                total_entries,
+                                current_path: result.zip_path.clone(),
+                                bytes_written: bytes_processed,
+                                total_bytes,
+                            },
+                        )
+                        .map_err(|e| format!("Failed to emit progress: {}", e))?;
+                }
+            }
+        }
+
+        for handle in join_handles {
+            handle.join().map_err(|_| "Compression worker thread panicked".to_string())?;
+        }
+
+        if enable_manifest {
+            let manifest_json = serde_json::to_vec(&manifest_entries).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+            let crc32 = crc32_of(&manifest_json);
+            raw_zip
+                .add_file(MANIFEST_NAME, METHOD_STORED, crc32, &manifest_json, manifest_json.len() as u64)
+                .map_err(|e| format!("Zip error for {}: {}", MANIFEST_NAME, e))?;
+        }
+
+        raw_zip.finish().map_err(|e| format!("Failed to finalize zip: {}", e))?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[cfg(test)]
+mod raw_zip_writer_tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn crc32_matches_the_standard_check_value() {
+        // The well-known CRC-32/ISO-HDLC check value for the ASCII string "123456789".
+        assert_eq!(crc32_of(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn raw_zip_writer_round_trips_through_the_zip_crate() {
+        let buf: Vec<u8> = Vec::new();
+        let mut raw_zip = RawZipWriter::new(Cursor::new(buf));
+
+        raw_zip.add_directory("docs").unwrap();
+
+        let (method, crc32, compressed, _) = compress_payload(b"hello, deflate", METHOD_DEFLATED, Some(6), false);
+        raw_zip.add_file("docs/hello.txt", method, crc32, &compressed, b"hello, deflate".len() as u64).unwrap();
+
+        let mut handle = raw_zip.begin_streamed_file("docs/stored.bin", false).unwrap();
+        raw_zip.write_streamed_chunk(&mut handle, b"stored bytes").unwrap();
+        raw_zip.finish_streamed_file(handle).unwrap();
+
+        let bytes = raw_zip.finish().unwrap().into_inner();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).expect("output should be a valid zip archive");
+        assert_eq!(archive.len(), 3);
+
+        let mut deflated = archive.by_name("docs/hello.txt").unwrap();
+        let mut contents = String::new();
+        deflated.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "hello, deflate");
+        drop(deflated);
+
+        let mut stored = archive.by_name("docs/stored.bin").unwrap();
+        let mut contents = String::new();
+        stored.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "stored bytes");
+        drop(stored);
+
+        let dir = archive.by_name("docs/").unwrap();
+        assert!(dir.is_dir());
+    }
+}
+
+// Exercises the whole point of chunk0-6: that `import_project_zip` actually catches an
+// archive whose manifest doesn't match what's really in it, instead of silently extracting
+// tampered/truncated bytes.
+#[cfg(test)]
+mod manifest_verification_tests {
+    use super::*;
+    use crate::import_project_zip;
+    use tauri::async_runtime::block_on;
+
+    fn scratch_path(name: &str, suffix: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("sync-desktop-poc-test-{}-{}{}", name, std::process::id(), suffix));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
+    fn real_sha256(content: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(content);
+        hex_digest(hasher)
+    }
+
+    // Builds a minimal archive with one Stored entry plus a `manifest.json` claiming
+    // `manifest_sha256` for it, so tests can choose whether that claim matches reality.
+    fn build_archive(zip_path: &Path, content: &[u8], manifest_sha256: &str) {
+        let file = File::create(zip_path).unwrap();
+        let mut raw_zip = RawZipWriter::new(file);
+
+        let crc32 = crc32_of(content);
+        raw_zip.add_file("data.txt", METHOD_STORED, crc32, content, content.len() as u64).unwrap();
+
+        let manifest_entries = vec![ManifestEntry {
+            path: "data.txt".to_string(),
+            sha256: manifest_sha256.to_string(),
+            length: content.len() as u64,
+        }];
+        let manifest_json = serde_json::to_vec(&manifest_entries).unwrap();
+        let manifest_crc32 = crc32_of(&manifest_json);
+        raw_zip
+            .add_file(MANIFEST_NAME, METHOD_STORED, manifest_crc32, &manifest_json, manifest_json.len() as u64)
+            .unwrap();
+
+        raw_zip.finish().unwrap();
+    }
+
+    #[test]
+    fn import_rejects_an_entry_that_does_not_match_the_manifest() {
+        let content = b"hello world";
+        let zip_path = scratch_path("tamper", ".zip");
+        let dest_dir = scratch_path("tamper", "-dest");
+
+        // The manifest claims a digest that doesn't match the (untouched) bytes actually
+        // sitting in the archive -- standing in for an entry that got swapped or corrupted
+        // after export.
+        build_archive(&zip_path, content, &"0".repeat(64));
+
+        let result = block_on(import_project_zip(
+            zip_path.to_string_lossy().into_owned(),
+            dest_dir.to_string_lossy().into_owned(),
+        ));
+
+        let err = result.expect_err("a checksum mismatch should fail the import");
+        assert!(err.contains("Checksum mismatch for data.txt"), "unexpected error: {}", err);
+        assert!(err.contains(&real_sha256(content)), "error should name the actual digest: {}", err);
+
+        let _ = fs::remove_file(&zip_path);
+        let _ = fs::remove_dir_all(&dest_dir);
+    }
+
+    #[test]
+    fn import_succeeds_when_the_manifest_matches() {
+        let content = b"hello world";
+        let zip_path = scratch_path("match", ".zip");
+        let dest_dir = scratch_path("match", "-dest");
+
+        build_archive(&zip_path, content, &real_sha256(content));
+
+        let result = block_on(import_project_zip(
+            zip_path.to_string_lossy().into_owned(),
+            dest_dir.to_string_lossy().into_owned(),
+        ));
+
+        let extracted = result.expect("import should succeed when the manifest matches");
+        assert_eq!(extracted.len(), 1);
+        assert_eq!(fs::read(dest_dir.join("data.txt")).unwrap(), content);
+
+        let _ = fs::remove_file(&zip_path);
+        let _ = fs::remove_dir_all(&dest_dir);
+    }
+}
@@ -0,0 +1,189 @@
+use std::fs::File;
+use std::io::Read;
+
+use tauri::http::{Request, Response, StatusCode};
+
+// Stored (uncompressed) entries sit at a known offset/length inside the archive, so a ranged
+// request against one only needs to decompress (i.e. copy) up through the end of the requested
+// range rather than the whole entry. Deflated entries can't be seeked into without re-running
+// the inflate from the start, so for those a range request still has to decode the whole entry
+// up to the requested end — but never past it, which is the part that matters for scrubbing
+// through a large file sequentially from the beginning.
+
+// Handles `zip://<archive path>/<entry path>` so the frontend can point a `<video>`/`<img>` tag
+// straight at an entry inside a project archive instead of extracting it first. Registered
+// alongside the other plugins in `run()`.
+pub fn handle(request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let uri = request.uri();
+    let archive_path = percent_decode(uri.host().unwrap_or(""));
+    let entry_path = percent_decode(uri.path().trim_start_matches('/'));
+
+    let Ok(file) = File::open(&archive_path) else {
+        return not_found();
+    };
+    let Ok(mut archive) = zip::ZipArchive::new(file) else {
+        return not_found();
+    };
+    let Ok(mut entry) = archive.by_name(&entry_path) else {
+        return not_found();
+    };
+    if entry.is_dir() {
+        return not_found();
+    }
+
+    // `size()` comes from the zip metadata, so it's known without decompressing anything.
+    let total_size = entry.size();
+    let mime = mime_guess::from_path(&entry_path).first_or_octet_stream();
+
+    // `and_then` (not `map`) flattens the "no/invalid header" case together with
+    // `parse_range`'s own "couldn't parse this spec" case into a single outer `None` — both
+    // mean the same thing here: ignore the Range header and serve the whole entry with 200,
+    // per RFC 7233 ("a server ... MAY ignore the Range header"). Only a spec that parsed but
+    // doesn't fit the entry (`Some(None)`) is actually unsatisfiable (416).
+    let range: Option<Option<(u64, u64)>> = request
+        .headers()
+        .get("range")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|header| parse_range(header, total_size));
+
+    match range {
+        Some(Some((start, end))) => {
+            // Only decode through the end of the requested range, not the whole entry: a
+            // scrub to an early timestamp in a multi-GB video shouldn't pay to inflate the
+            // rest of the file just to throw it away.
+            let mut prefix = vec![0u8; (end + 1) as usize];
+            if entry.read_exact(&mut prefix).is_err() {
+                return not_found();
+            }
+            let body = prefix.split_off(start as usize);
+
+            Response::builder()
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header("Content-Type", mime.essence_str())
+                .header("Accept-Ranges", "bytes")
+                .header("Content-Range", format!("bytes {}-{}/{}", start, end, total_size))
+                .header("Content-Length", body.len().to_string())
+                .body(body)
+                .unwrap_or_else(|_| not_found())
+        }
+        Some(None) => Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header("Content-Range", format!("bytes */{}", total_size))
+            .body(Vec::new())
+            .unwrap_or_else(|_| not_found()),
+        None => {
+            let mut data = Vec::with_capacity(total_size as usize);
+            if entry.read_to_end(&mut data).is_err() {
+                return not_found();
+            }
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("Content-Type", mime.essence_str())
+                .header("Accept-Ranges", "bytes")
+                .header("Content-Length", total_size.to_string())
+                .body(data)
+                .unwrap_or_else(|_| not_found())
+        }
+    }
+}
+
+fn not_found() -> Response<Vec<u8>> {
+    Response::builder().status(StatusCode::NOT_FOUND).body(Vec::new()).unwrap()
+}
+
+// Parses a single-range `Range: bytes=start-end` header against `total_size`. This only
+// understands a single `bytes=` range (no multi-range lists like "0-10,20-30", no other
+// units), so the outer `None` covers both "no Range header" and "a spec this parser doesn't
+// understand" — both mean the same thing to the caller: ignore it and serve the whole entry
+// with 200, per RFC 7233. `Some(None)` means the spec parsed but doesn't fit inside the
+// entry (416). `Some(Some((start, end)))` is a satisfiable range.
+fn parse_range(header: &str, total_size: u64) -> Option<Option<(u64, u64)>> {
+    let spec = header.strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start_str, end_str) = spec.split_once('-')?;
+
+    let (start, end) = if start_str.is_empty() {
+        // Suffix range: "bytes=-500" means the last 500 bytes. A suffix longer than the
+        // entry just clamps to the whole thing, per RFC 7233.
+        let suffix_len: u64 = end_str.parse().ok()?;
+        let start = total_size.saturating_sub(suffix_len);
+        (start, total_size.saturating_sub(1))
+    } else {
+        let start: u64 = start_str.parse().ok()?;
+        let end = if end_str.is_empty() {
+            total_size.saturating_sub(1)
+        } else {
+            end_str.parse().ok()?
+        };
+        (start, end)
+    };
+
+    if start > end || end >= total_size {
+        Some(None)
+    } else {
+        Some(Some((start, end)))
+    }
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or(""), 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod parse_range_tests {
+    use super::parse_range;
+
+    #[test]
+    fn parses_a_simple_range() {
+        assert_eq!(parse_range("bytes=2-5", 10), Some(Some((2, 5))));
+    }
+
+    #[test]
+    fn parses_an_open_ended_range() {
+        assert_eq!(parse_range("bytes=2-", 10), Some(Some((2, 9))));
+    }
+
+    #[test]
+    fn clamps_a_suffix_range_larger_than_the_entry() {
+        // "last 1000 bytes" of a 10-byte entry just means the whole entry.
+        assert_eq!(parse_range("bytes=-1000", 10), Some(Some((0, 9))));
+    }
+
+    #[test]
+    fn out_of_bounds_range_is_unsatisfiable() {
+        assert_eq!(parse_range("bytes=50-60", 10), Some(None));
+    }
+
+    #[test]
+    fn multi_range_requests_are_ignored_not_rejected() {
+        // This parser only understands a single range; per RFC 7233 the server should ignore
+        // a spec it doesn't understand and serve the whole entry (200), not 416.
+        assert_eq!(parse_range("bytes=0-10,20-30", 100), None);
+    }
+
+    #[test]
+    fn non_bytes_units_are_ignored_not_rejected() {
+        assert_eq!(parse_range("items=0-10", 100), None);
+    }
+
+    #[test]
+    fn garbage_is_ignored_not_rejected() {
+        assert_eq!(parse_range("bytes=not-a-range", 100), None);
+    }
+}